@@ -5,11 +5,17 @@ use tiny_keccak::{Hasher, Keccak};
 /// Matches Salvium C++ cn_fast_hash / keccak()
 #[wasm_bindgen]
 pub fn keccak256(data: &[u8]) -> Vec<u8> {
+    keccak256_32(data).to_vec()
+}
+
+/// Keccak-256 hash returning a fixed-size 32-byte array (internal helper to
+/// avoid `Vec` allocation in the hot Merkle tree loop).
+fn keccak256_32(data: &[u8]) -> [u8; 32] {
     let mut keccak = Keccak::v256();
     let mut output = [0u8; 32];
     keccak.update(data);
     keccak.finalize(&mut output);
-    output.to_vec()
+    output
 }
 
 /// Blake2b with variable output length (unkeyed)
@@ -35,3 +41,292 @@ pub fn blake2b_keyed(data: &[u8], out_len: usize, key: &[u8]) -> Vec<u8> {
         .as_bytes()
         .to_vec()
 }
+
+/// Blake2b with key, salt, and personalization (RFC 7693 extended parameters)
+/// Empty slices mean "unset" for that parameter.
+/// Used by domain-separated protocols that set Blake2b's native personal/salt
+/// fields instead of prefixing the message with a context string.
+#[wasm_bindgen]
+pub fn blake2b_personalized(
+    data: &[u8],
+    out_len: usize,
+    key: &[u8],
+    salt: &[u8],
+    personal: &[u8],
+) -> Vec<u8> {
+    let mut params = blake2b_simd::Params::new();
+    params.hash_length(out_len);
+    if !key.is_empty() {
+        params.key(key);
+    }
+    if !salt.is_empty() {
+        params.salt(salt);
+    }
+    if !personal.is_empty() {
+        params.personal(personal);
+    }
+    params.hash(data).as_bytes().to_vec()
+}
+
+/// CryptoNote Merkle tree root over a flat buffer of N concatenated 32-byte
+/// hashes, matching Monero/Salvium `tree_hash`.
+/// Matches Salvium C++ `crypto::tree_hash`.
+#[wasm_bindgen]
+pub fn tree_hash(hashes: &[u8]) -> Result<Vec<u8>, JsValue> {
+    if hashes.is_empty() || hashes.len() % 32 != 0 {
+        return Err(JsValue::from_str(
+            "tree_hash: input length must be a non-zero multiple of 32",
+        ));
+    }
+
+    let count = hashes.len() / 32;
+    let leaf = |i: usize| -> [u8; 32] {
+        let mut h = [0u8; 32];
+        h.copy_from_slice(&hashes[i * 32..i * 32 + 32]);
+        h
+    };
+
+    if count == 1 {
+        return Ok(leaf(0).to_vec());
+    }
+    if count == 2 {
+        let mut buf = [0u8; 64];
+        buf[..32].copy_from_slice(&leaf(0));
+        buf[32..].copy_from_slice(&leaf(1));
+        return Ok(keccak256_32(&buf).to_vec());
+    }
+
+    // Largest power of two strictly less than count.
+    let mut cnt = 1usize;
+    while cnt * 2 < count {
+        cnt <<= 1;
+    }
+
+    let mut ints = vec![[0u8; 32]; cnt];
+    let overlap = 2 * cnt - count;
+    for i in 0..overlap {
+        ints[i] = leaf(i);
+    }
+    let mut src = overlap;
+    for dst in overlap..cnt {
+        let mut buf = [0u8; 64];
+        buf[..32].copy_from_slice(&leaf(src));
+        buf[32..].copy_from_slice(&leaf(src + 1));
+        ints[dst] = keccak256_32(&buf);
+        src += 2;
+    }
+
+    while cnt > 2 {
+        cnt >>= 1;
+        for i in 0..cnt {
+            let mut buf = [0u8; 64];
+            buf[..32].copy_from_slice(&ints[2 * i]);
+            buf[32..].copy_from_slice(&ints[2 * i + 1]);
+            ints[i] = keccak256_32(&buf);
+        }
+    }
+
+    let mut buf = [0u8; 64];
+    buf[..32].copy_from_slice(&ints[0]);
+    buf[32..].copy_from_slice(&ints[1]);
+    Ok(keccak256_32(&buf).to_vec())
+}
+
+/// Split a concatenated buffer into segments per a length table, validating
+/// that the lengths exactly cover the buffer.
+fn split_segments<'a>(inputs: &'a [u8], lengths: &[u32]) -> Result<Vec<&'a [u8]>, JsValue> {
+    let mut segments = Vec::with_capacity(lengths.len());
+    let mut offset = 0usize;
+    for &len in lengths {
+        let end = offset
+            .checked_add(len as usize)
+            .filter(|&end| end <= inputs.len())
+            .ok_or_else(|| JsValue::from_str("batch: lengths exceed input buffer"))?;
+        segments.push(&inputs[offset..end]);
+        offset = end;
+    }
+    if offset != inputs.len() {
+        return Err(JsValue::from_str(
+            "batch: lengths do not cover the entire input buffer",
+        ));
+    }
+    Ok(segments)
+}
+
+#[cfg(all(feature = "parallel", not(target_arch = "wasm32")))]
+fn keccak256_many(segments: &[&[u8]]) -> Vec<u8> {
+    use rayon::prelude::*;
+    segments
+        .par_iter()
+        .map(|seg| keccak256_32(seg))
+        .collect::<Vec<_>>()
+        .concat()
+}
+
+#[cfg(not(all(feature = "parallel", not(target_arch = "wasm32"))))]
+fn keccak256_many(segments: &[&[u8]]) -> Vec<u8> {
+    segments.iter().flat_map(|seg| keccak256_32(seg)).collect()
+}
+
+#[cfg(all(feature = "parallel", not(target_arch = "wasm32")))]
+fn blake2b_many(segments: &[&[u8]], out_len: usize) -> Vec<u8> {
+    use rayon::prelude::*;
+    segments
+        .par_iter()
+        .flat_map(|seg| {
+            blake2b_simd::Params::new()
+                .hash_length(out_len)
+                .hash(seg)
+                .as_bytes()
+                .to_vec()
+        })
+        .collect()
+}
+
+#[cfg(not(all(feature = "parallel", not(target_arch = "wasm32"))))]
+fn blake2b_many(segments: &[&[u8]], out_len: usize) -> Vec<u8> {
+    segments
+        .iter()
+        .flat_map(|seg| {
+            blake2b_simd::Params::new()
+                .hash_length(out_len)
+                .hash(seg)
+                .as_bytes()
+                .to_vec()
+        })
+        .collect()
+}
+
+/// Hash each length-delimited segment of a concatenated buffer with
+/// Keccak-256 in one call, returning the concatenated 32-byte digests.
+/// Amortizes the JS/WASM boundary crossing cost when hashing many inputs
+/// (e.g. verifying hundreds of outputs/key images during wallet scanning).
+///
+/// `lengths` gives the byte length of each segment of `inputs`, in order;
+/// the lengths must sum to exactly `inputs.len()`. On native builds with the
+/// `parallel` feature enabled, segments are hashed concurrently with rayon;
+/// under wasm this is always a sequential loop.
+#[wasm_bindgen]
+pub fn keccak256_batch(inputs: &[u8], lengths: &[u32]) -> Result<Vec<u8>, JsValue> {
+    let segments = split_segments(inputs, lengths)?;
+    Ok(keccak256_many(&segments))
+}
+
+/// Hash each length-delimited segment of a concatenated buffer with
+/// unkeyed Blake2b in one call, returning the concatenated `out_len`-byte
+/// digests. See `keccak256_batch` for the segment layout and parallelism
+/// notes.
+#[wasm_bindgen]
+pub fn blake2b_hash_batch(inputs: &[u8], lengths: &[u32], out_len: usize) -> Result<Vec<u8>, JsValue> {
+    let segments = split_segments(inputs, lengths)?;
+    Ok(blake2b_many(&segments, out_len))
+}
+
+/// Incremental Keccak-256 hasher for streaming large or composite preimages
+/// without materializing the whole input in one `&[u8]`.
+#[wasm_bindgen]
+pub struct KeccakHasher {
+    inner: Keccak,
+    out_len: usize,
+}
+
+#[wasm_bindgen]
+impl KeccakHasher {
+    /// Create a new hasher producing `out_len` bytes on `finalize()`.
+    #[wasm_bindgen(constructor)]
+    pub fn new(out_len: usize) -> KeccakHasher {
+        KeccakHasher {
+            inner: Keccak::v256(),
+            out_len,
+        }
+    }
+
+    /// Feed more input bytes into the running hash state.
+    pub fn update(&mut self, data: &[u8]) {
+        self.inner.update(data);
+    }
+
+    /// Consume the hasher and return the final digest.
+    pub fn finalize(self) -> Vec<u8> {
+        let mut output = vec![0u8; self.out_len];
+        self.inner.finalize(&mut output);
+        output
+    }
+}
+
+/// Incremental Blake2b hasher (unkeyed or keyed) for streaming large or
+/// composite preimages without materializing the whole input in one `&[u8]`.
+#[wasm_bindgen]
+pub struct Blake2bHasher {
+    inner: blake2b_simd::State,
+}
+
+#[wasm_bindgen]
+impl Blake2bHasher {
+    /// Create a new unkeyed hasher producing `out_len` bytes on `finalize()`.
+    #[wasm_bindgen(constructor)]
+    pub fn new(out_len: usize) -> Blake2bHasher {
+        Blake2bHasher {
+            inner: blake2b_simd::Params::new().hash_length(out_len).to_state(),
+        }
+    }
+
+    /// Create a new keyed hasher producing `out_len` bytes on `finalize()`.
+    pub fn new_keyed(out_len: usize, key: &[u8]) -> Blake2bHasher {
+        Blake2bHasher {
+            inner: blake2b_simd::Params::new()
+                .hash_length(out_len)
+                .key(key)
+                .to_state(),
+        }
+    }
+
+    /// Feed more input bytes into the running hash state.
+    pub fn update(&mut self, data: &[u8]) {
+        self.inner.update(data);
+    }
+
+    /// Consume the hasher and return the final digest.
+    pub fn finalize(self) -> Vec<u8> {
+        self.inner.finalize().as_bytes().to_vec()
+    }
+}
+
+/// BLAKE3 hash with arbitrary output length (regular, unkeyed mode)
+/// `out_len` may exceed 32 bytes; output is squeezed from BLAKE3's XOF
+/// rather than truncating a fixed digest.
+#[wasm_bindgen]
+pub fn blake3_hash(data: &[u8], out_len: usize) -> Vec<u8> {
+    blake3_squeeze(blake3::Hasher::new().update(data), out_len)
+}
+
+/// BLAKE3 keyed hash (MAC mode) with arbitrary output length
+/// `key32` must be exactly 32 bytes.
+#[wasm_bindgen]
+pub fn blake3_keyed(key32: &[u8], data: &[u8], out_len: usize) -> Result<Vec<u8>, JsValue> {
+    let key: [u8; 32] = key32
+        .try_into()
+        .map_err(|_| JsValue::from_str("blake3_keyed: key must be exactly 32 bytes"))?;
+    Ok(blake3_squeeze(
+        blake3::Hasher::new_keyed(&key).update(data),
+        out_len,
+    ))
+}
+
+/// BLAKE3 key derivation mode with arbitrary output length
+/// `context` should be a hardcoded, application-specific constant string (per
+/// BLAKE3's KDF contract); `key_material` is the input keying material.
+#[wasm_bindgen]
+pub fn blake3_derive_key(context: &str, key_material: &[u8], out_len: usize) -> Vec<u8> {
+    blake3_squeeze(
+        blake3::Hasher::new_derive_key(context).update(key_material),
+        out_len,
+    )
+}
+
+/// Squeeze `out_len` bytes from a finalized BLAKE3 hasher's extendable output.
+fn blake3_squeeze(hasher: &mut blake3::Hasher, out_len: usize) -> Vec<u8> {
+    let mut output = vec![0u8; out_len];
+    hasher.finalize_xof().fill(&mut output);
+    output
+}